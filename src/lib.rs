@@ -1,17 +1,25 @@
+mod retry;
+mod sink;
 mod split;
 
+pub use retry::Retry;
+pub use sink::MultipartUploadSink;
+pub use split::PartLimitExceeded;
+
 use bytes::Bytes;
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt, TryStreamExt};
-use rusoto_core::{ByteStream, RusotoError};
+use rusoto_core::{ByteStream, HttpDispatchError, RusotoError};
 use rusoto_s3::{
     AbortMultipartUploadRequest, CompleteMultipartUploadError, CompleteMultipartUploadOutput,
     CompleteMultipartUploadRequest, CompletedMultipartUpload, CompletedPart,
-    CreateMultipartUploadError, CreateMultipartUploadRequest, UploadPartError, UploadPartRequest,
-    S3,
+    CreateMultipartUploadError, CreateMultipartUploadRequest, ListPartsError, ListPartsRequest,
+    Part as ListedPart, UploadPartError, UploadPartRequest, S3,
 };
 use std::future::Future;
 use std::ops::RangeInclusive;
-use std::task::Poll;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 // https://docs.aws.amazon.com/AmazonS3/latest/userguide/qfacts.html
 pub const PART_SIZE: RangeInclusive<usize> = 5 << 20..=5 << 30;
@@ -23,39 +31,270 @@ where
     pub body: B,
     pub bucket: String,
     pub key: String,
+    pub on_progress: Option<Arc<dyn Fn(UploadProgress) + Send + Sync>>,
+}
+
+/// Reported after each part finishes uploading, in completion order (not necessarily part order).
+#[derive(Clone, Copy, Debug)]
+pub struct UploadProgress {
+    pub part_number: usize,
+    pub part_bytes: usize,
+    pub bytes_uploaded: usize,
+    pub parts_completed: usize,
 }
 
 pub type MultipartUploadOutput = CompleteMultipartUploadOutput;
 
+/// `resume`'s `list_parts` call returned part numbers that don't form a contiguous `1..=n` run.
+/// Under `concurrency_limit > 1`, parts are dispatched concurrently, so S3 can durably record a
+/// higher-numbered part before an earlier sibling finishes (or while it's still retrying) — a
+/// process killed mid-upload can leave a genuine gap (e.g. parts `{1, 3, 4}` with `2` missing).
+/// Trusting `list_parts`' reported sizes in that case would silently reconstruct the object with
+/// the missing part's byte range cut out, so `resume` reports this error instead of guessing.
+#[derive(Debug)]
+pub struct NonContiguousParts;
+
+impl std::fmt::Display for NonContiguousParts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "list_parts returned a non-contiguous part range; cannot safely resume"
+        )
+    }
+}
+
+impl std::error::Error for NonContiguousParts {}
+
+/// Uploads `input.body` to `bucket`/`key` via S3 multipart upload, retrying per `retry` and
+/// reporting progress through `input.on_progress`.
+///
+/// # Checksums
+///
+/// There's no way to select a checksum algorithm (SHA-256/CRC32C) here, only the hardcoded
+/// `content_md5` already sent on every part: `rusoto_s3` 0.48's `CreateMultipartUploadRequest`
+/// and `UploadPartRequest` don't have `checksum_algorithm` or any `checksum_*` fields to set, so
+/// there is nothing to wire up without vendoring a newer `rusoto_s3`. Treat selectable checksums
+/// as rejected-infeasible on this dependency version rather than a gap to fill.
 pub async fn multipart_upload<C, B, E>(
     client: &C,
     input: MultipartUploadRequest<B, E>,
     part_size: RangeInclusive<usize>,
     concurrency_limit: Option<usize>,
+    retry: Option<Retry>,
+    part_timeout: Option<Duration>,
+    adaptive_part_size: bool,
 ) -> Result<MultipartUploadOutput, E>
 where
-    C: S3,
+    C: S3 + Sync,
     B: Stream<Item = Result<Bytes, E>>,
     E: From<RusotoError<CreateMultipartUploadError>>
         + From<RusotoError<UploadPartError>>
-        + From<RusotoError<CompleteMultipartUploadError>>,
+        + From<RusotoError<CompleteMultipartUploadError>>
+        + From<split::PartLimitExceeded>,
 {
-    let MultipartUploadRequest { body, bucket, key } = input;
+    let MultipartUploadRequest {
+        body,
+        bucket,
+        key,
+        on_progress,
+    } = input;
 
-    let output = client
-        .create_multipart_upload(CreateMultipartUploadRequest {
+    let output = retry::with_retry(retry, || {
+        client.create_multipart_upload(CreateMultipartUploadRequest {
             bucket: bucket.clone(),
             key: key.clone(),
             ..CreateMultipartUploadRequest::default()
         })
+    })
+    .await?;
+    let upload_id = output.upload_id.unwrap();
+
+    run(
+        client,
+        body,
+        bucket,
+        key,
+        upload_id,
+        Vec::new(),
+        0,
+        0,
+        *part_size.start(),
+        part_size,
+        concurrency_limit,
+        retry,
+        part_timeout,
+        adaptive_part_size,
+        on_progress,
+    )
+    .await
+}
+
+/// Resumes a multipart upload that was already started with `upload_id`, picking up after the
+/// parts S3 reports via `list_parts`. `body` must be the *same, untrimmed* stream the original
+/// upload would have read, starting at its first byte — the bytes S3 already has are computed
+/// here and discarded internally via `split`'s `skip_bytes`, not pre-trimmed by the caller.
+/// `split` is also told to number new parts starting after the highest part number already
+/// uploaded.
+#[allow(clippy::too_many_arguments)]
+pub async fn resume<C, B, E>(
+    client: &C,
+    input: MultipartUploadRequest<B, E>,
+    upload_id: String,
+    part_size: RangeInclusive<usize>,
+    concurrency_limit: Option<usize>,
+    retry: Option<Retry>,
+    part_timeout: Option<Duration>,
+    adaptive_part_size: bool,
+) -> Result<MultipartUploadOutput, E>
+where
+    C: S3 + Sync,
+    B: Stream<Item = Result<Bytes, E>>,
+    E: From<RusotoError<CreateMultipartUploadError>>
+        + From<RusotoError<UploadPartError>>
+        + From<RusotoError<CompleteMultipartUploadError>>
+        + From<RusotoError<ListPartsError>>
+        + From<split::PartLimitExceeded>
+        + From<NonContiguousParts>,
+{
+    let MultipartUploadRequest {
+        body,
+        bucket,
+        key,
+        on_progress,
+    } = input;
+
+    let mut completed_parts = Vec::new();
+    let mut skip_bytes = 0;
+    let mut start_part_number = 0;
+    let mut start_part_size = *part_size.start();
+    let mut part_number_marker = None;
+
+    loop {
+        let output = retry::with_retry(retry, || {
+            client.list_parts(ListPartsRequest {
+                bucket: bucket.clone(),
+                key: key.clone(),
+                upload_id: upload_id.clone(),
+                part_number_marker,
+                ..ListPartsRequest::default()
+            })
+        })
         .await?;
-    let upload_id = output.upload_id.as_ref().unwrap();
 
-    let futures = split::split(body, part_size).map_ok(|part| {
-        client
-            .upload_part(UploadPartRequest {
+        merge_list_parts_page(
+            output.parts.unwrap_or_default(),
+            &mut completed_parts,
+            &mut skip_bytes,
+            &mut start_part_number,
+            &mut start_part_size,
+        );
+
+        if output.is_truncated != Some(true) {
+            break;
+        }
+        part_number_marker = output.next_part_number_marker;
+    }
+
+    let mut part_numbers: Vec<i64> = completed_parts
+        .iter()
+        .map(|completed_part| completed_part.part_number.unwrap_or_default())
+        .collect();
+    part_numbers.sort_unstable();
+    let is_contiguous = part_numbers
+        .iter()
+        .enumerate()
+        .all(|(i, &part_number)| part_number == i as i64 + 1);
+    if !is_contiguous {
+        return Err(E::from(NonContiguousParts));
+    }
+
+    run(
+        client,
+        body,
+        bucket,
+        key,
+        upload_id,
+        completed_parts,
+        start_part_number,
+        skip_bytes,
+        start_part_size,
+        part_size,
+        concurrency_limit,
+        retry,
+        part_timeout,
+        adaptive_part_size,
+        on_progress,
+    )
+    .await
+}
+
+/// Folds one `list_parts` page into `resume`'s running state: the parts already uploaded (as
+/// `CompletedPart`s, order not yet sorted), how many leading bytes of `body` to skip, the part
+/// number to resume numbering after, and the largest individual part size seen so far (fed to
+/// `split` as its adaptive starting point).
+fn merge_list_parts_page(
+    parts: Vec<ListedPart>,
+    completed_parts: &mut Vec<CompletedPart>,
+    skip_bytes: &mut usize,
+    start_part_number: &mut usize,
+    start_part_size: &mut usize,
+) {
+    for part in parts {
+        let part_size_bytes = part.size.unwrap_or_default() as usize;
+        *skip_bytes += part_size_bytes;
+        *start_part_number = (*start_part_number).max(part.part_number.unwrap_or_default() as usize);
+        *start_part_size = (*start_part_size).max(part_size_bytes);
+        completed_parts.push(CompletedPart {
+            e_tag: part.e_tag,
+            part_number: part.part_number,
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run<C, B, E>(
+    client: &C,
+    body: B,
+    bucket: String,
+    key: String,
+    upload_id: String,
+    mut completed_parts: Vec<CompletedPart>,
+    start_part_number: usize,
+    skip_bytes: usize,
+    start_part_size: usize,
+    part_size: RangeInclusive<usize>,
+    concurrency_limit: Option<usize>,
+    retry: Option<Retry>,
+    part_timeout: Option<Duration>,
+    adaptive_part_size: bool,
+    on_progress: Option<Arc<dyn Fn(UploadProgress) + Send + Sync>>,
+) -> Result<MultipartUploadOutput, E>
+where
+    C: S3 + Sync,
+    B: Stream<Item = Result<Bytes, E>>,
+    E: From<RusotoError<CreateMultipartUploadError>>
+        + From<RusotoError<UploadPartError>>
+        + From<RusotoError<CompleteMultipartUploadError>>
+        + From<split::PartLimitExceeded>,
+{
+    let futures = split::split(
+        body,
+        part_size,
+        adaptive_part_size,
+        start_part_number,
+        skip_bytes,
+        start_part_size,
+    )
+    .map_ok(|part| {
+        let part_number = part.part_number;
+        let part_bytes = part.content_length;
+        let bucket = bucket.clone();
+        let key = key.clone();
+        let upload_id = upload_id.clone();
+        retry::with_retry(retry, move || {
+            let request = UploadPartRequest {
                 body: Some(ByteStream::new(futures::stream::iter(
-                    part.body.into_iter().map(Ok),
+                    part.body.clone().into_iter().map(Ok),
                 ))),
                 bucket: bucket.clone(),
                 content_length: Some(part.content_length as _),
@@ -64,32 +303,48 @@ where
                 part_number: part.part_number as _,
                 upload_id: upload_id.clone(),
                 ..UploadPartRequest::default()
-            })
-            .map_ok({
-                let part_number = part.part_number;
-                move |output| CompletedPart {
+            };
+            with_part_timeout(part_timeout, client.upload_part(request))
+        })
+        .map_ok(move |output| {
+            (
+                CompletedPart {
                     e_tag: output.e_tag,
                     part_number: Some(part_number as _),
-                }
-            })
-            .err_into()
+                },
+                part_bytes,
+            )
+        })
+        .err_into()
+        .boxed()
     });
 
     (async {
-        let mut completed_parts = dispatch_concurrent(futures, concurrency_limit).await?;
+        let parts_completed_already = completed_parts.len();
+        completed_parts.extend(
+            dispatch_concurrent(
+                futures,
+                concurrency_limit,
+                on_progress,
+                skip_bytes,
+                parts_completed_already,
+            )
+            .await?,
+        );
         completed_parts.sort_by_key(|completed_part| completed_part.part_number);
 
-        let output = client
-            .complete_multipart_upload(CompleteMultipartUploadRequest {
+        let output = retry::with_retry(retry, || {
+            client.complete_multipart_upload(CompleteMultipartUploadRequest {
                 bucket: bucket.clone(),
                 key: key.clone(),
                 multipart_upload: Some(CompletedMultipartUpload {
-                    parts: Some(completed_parts),
+                    parts: Some(completed_parts.clone()),
                 }),
                 upload_id: upload_id.clone(),
                 ..CompleteMultipartUploadRequest::default()
             })
-            .await?;
+        })
+        .await?;
 
         Ok(output)
     })
@@ -106,10 +361,76 @@ where
     .await
 }
 
-async fn dispatch_concurrent<S, F, T, E>(stream: S, limit: Option<usize>) -> Result<Vec<T>, E>
+// A stalled `upload_part` would otherwise hold a `concurrency_limit` slot forever; an elapsed
+// timeout is surfaced as a dispatch error so `retry::with_retry` treats it as retryable.
+// `RusotoError` is defined upstream and too large to box away without changing every caller's
+// error type.
+#[allow(clippy::result_large_err)]
+pub(crate) async fn with_part_timeout<Fut, T, E>(
+    part_timeout: Option<Duration>,
+    fut: Fut,
+) -> Result<T, RusotoError<E>>
+where
+    Fut: Future<Output = Result<T, RusotoError<E>>>,
+{
+    match part_timeout {
+        Some(duration) => tokio::time::timeout(duration, fut).await.unwrap_or_else(|_| {
+            Err(RusotoError::HttpDispatch(HttpDispatchError::new(
+                "upload_part timed out".to_string(),
+            )))
+        }),
+        None => fut.await,
+    }
+}
+
+/// Polls every future in `futures` once, removing each that completes and passing its output to
+/// `on_complete`. Shared by [`dispatch_concurrent`] (which feeds futures in from a `Stream`) and
+/// [`sink::MultipartUploadSink`] (which feeds futures in as its write buffer fills), so the two
+/// concurrency pools can't silently drift apart.
+pub(crate) fn poll_drain<F, T, E>(
+    futures: &mut Vec<F>,
+    cx: &mut Context<'_>,
+    mut on_complete: impl FnMut(T),
+) -> Poll<Result<(), E>>
 where
-    S: Stream<Item = Result<F, E>>,
     F: Future<Output = Result<T, E>> + Unpin,
+{
+    let mut is_pending = false;
+    let mut i = 0;
+    while i < futures.len() {
+        match futures[i].poll_unpin(cx) {
+            Poll::Ready(Ok(value)) => {
+                futures.swap_remove(i);
+                on_complete(value);
+            }
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => {
+                is_pending = true;
+                i += 1;
+            }
+        }
+    }
+    if is_pending {
+        Poll::Pending
+    } else {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Drives `stream`'s part-upload futures to completion, reporting [`UploadProgress`] through
+/// `on_progress` as each part finishes. `initial_bytes_uploaded`/`initial_parts_completed` seed
+/// the running totals so a resumed upload's progress reports include the parts S3 already had
+/// before `resume` was called, rather than resetting to zero.
+async fn dispatch_concurrent<S, F, E>(
+    stream: S,
+    limit: Option<usize>,
+    on_progress: Option<Arc<dyn Fn(UploadProgress) + Send + Sync>>,
+    initial_bytes_uploaded: usize,
+    initial_parts_completed: usize,
+) -> Result<Vec<CompletedPart>, E>
+where
+    S: Stream<Item = Result<F, E>>,
+    F: Future<Output = Result<(CompletedPart, usize), E>> + Unpin,
 {
     futures::pin_mut!(stream);
 
@@ -120,11 +441,13 @@ where
     let mut stream = stream.fuse();
     let mut futures = Vec::new();
     let mut outputs = Vec::new();
+    let mut bytes_uploaded = initial_bytes_uploaded;
+    let mut parts_completed = initial_parts_completed;
 
     futures::future::poll_fn(|cx| {
         while !stream.is_done() || !futures.is_empty() {
             let mut is_pending = false;
-            while limit.map_or(true, |limit| limit > futures.len()) {
+            while limit.is_none_or(|limit| limit > futures.len()) {
                 match stream.poll_next_unpin(cx) {
                     Poll::Ready(Some(Ok(future))) => futures.push(future),
                     Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
@@ -135,19 +458,22 @@ where
                     }
                 }
             }
-            let mut i = 0;
-            while i < futures.len() {
-                match futures[i].poll_unpin(cx) {
-                    Poll::Ready(Ok(output)) => {
-                        futures.swap_remove(i);
-                        outputs.push(output);
-                    }
-                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
-                    Poll::Pending => {
-                        is_pending = true;
-                        i += 1;
-                    }
+            match poll_drain(&mut futures, cx, |(completed_part, part_bytes)| {
+                bytes_uploaded += part_bytes;
+                parts_completed += 1;
+                if let Some(on_progress) = &on_progress {
+                    on_progress(UploadProgress {
+                        part_number: completed_part.part_number.unwrap_or_default() as _,
+                        part_bytes,
+                        bytes_uploaded,
+                        parts_completed,
+                    });
                 }
+                outputs.push(completed_part);
+            }) {
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Ready(Ok(())) => {}
+                Poll::Pending => is_pending = true,
             }
             if is_pending {
                 return Poll::Pending;
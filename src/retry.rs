@@ -0,0 +1,58 @@
+use rusoto_core::RusotoError;
+use std::future::Future;
+use std::time::Duration;
+
+/// Retry policy for each `upload_part` call, also applied to `create_multipart_upload`,
+/// `list_parts` and `complete_multipart_upload`.
+///
+/// Delays follow full-jitter exponential backoff: `sleep = random(0, min(max_delay, initial_delay * 2^attempt))`.
+#[derive(Clone, Copy, Debug)]
+pub struct Retry {
+    pub max_attempts: usize,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+pub(crate) fn is_retryable<E>(error: &RusotoError<E>) -> bool {
+    match error {
+        RusotoError::HttpDispatch(_) => true,
+        RusotoError::Unknown(response) => response.status.is_server_error(),
+        _ => false,
+    }
+}
+
+pub(crate) async fn with_retry<F, Fut, T, E>(retry: Option<Retry>, mut f: F) -> Result<T, RusotoError<E>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, RusotoError<E>>>,
+{
+    let retry = match retry {
+        Some(retry) => retry,
+        None => return f().await,
+    };
+
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Err(e) if attempt + 1 < retry.max_attempts && is_retryable(&e) => {
+                let cap = retry
+                    .initial_delay
+                    .saturating_mul(1u32 << attempt.min(31))
+                    .min(retry.max_delay);
+                tokio::time::sleep(cap.mul_f64(rand::random::<f64>())).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
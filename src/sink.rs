@@ -0,0 +1,445 @@
+use crate::{poll_drain, retry, split, with_part_timeout, MultipartUploadOutput, Retry, UploadProgress};
+use bytes::{Bytes, BytesMut};
+use futures::{FutureExt, TryFutureExt};
+use rusoto_core::{ByteStream, RusotoError};
+use rusoto_s3::{
+    AbortMultipartUploadRequest, CompleteMultipartUploadError, CompleteMultipartUploadRequest,
+    CompletedMultipartUpload, CompletedPart, CreateMultipartUploadError,
+    CreateMultipartUploadRequest, UploadPartError, UploadPartRequest, S3,
+};
+use std::future::Future;
+use std::io;
+use std::ops::RangeInclusive;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::AsyncWrite;
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+enum Phase<'a, E> {
+    Active,
+    // `E` is boxed so `Phase`, and with it `MultipartUploadSink`, stays `Unpin` regardless of
+    // whether the caller's error type is: `Pin<&mut Self>::get_mut()` below requires it.
+    Aborting(BoxFuture<'a, ()>, Box<E>),
+    ShuttingDown(BoxFuture<'a, Result<MultipartUploadOutput, E>>),
+    Done,
+}
+
+/// An `AsyncWrite` sink that buffers writes into parts and drives a multipart upload, for
+/// callers that produce data incrementally rather than already holding a ready-made `Stream`.
+///
+/// Dropping the sink before `poll_shutdown` completes leaves the upload un-aborted; call
+/// [`AsyncWriteExt::shutdown`](tokio::io::AsyncWriteExt::shutdown) to complete or abort it and
+/// [`MultipartUploadSink::take_output`] to retrieve the result.
+///
+/// Unlike `split`, the sink's part size doesn't grow adaptively, so a long-lived writer that
+/// would need more than 10,000 parts at the configured `part_size` aborts the upload with
+/// [`crate::PartLimitExceeded`] instead of silently emitting part numbers S3 would reject.
+pub struct MultipartUploadSink<'a, C, E> {
+    client: &'a C,
+    bucket: String,
+    key: String,
+    upload_id: String,
+    part_size: RangeInclusive<usize>,
+    concurrency_limit: Option<usize>,
+    retry: Option<Retry>,
+    part_timeout: Option<Duration>,
+    on_progress: Option<Arc<dyn Fn(UploadProgress) + Send + Sync>>,
+    buffer: BytesMut,
+    next_part_number: usize,
+    in_flight: Vec<BoxFuture<'a, Result<(CompletedPart, usize), E>>>,
+    completed_parts: Vec<CompletedPart>,
+    bytes_uploaded: usize,
+    parts_completed: usize,
+    phase: Phase<'a, E>,
+    // Boxed for the same reason as `Phase::Aborting`'s error: a bare `E` here would make the whole
+    // struct conditionally `Unpin`, and `poll_write`/`poll_flush`/`poll_shutdown` all need it to be.
+    output: Option<Result<MultipartUploadOutput, Box<E>>>,
+}
+
+impl<'a, C, E> MultipartUploadSink<'a, C, E>
+where
+    C: S3 + Sync,
+    E: From<RusotoError<CreateMultipartUploadError>>
+        + From<RusotoError<UploadPartError>>
+        + From<RusotoError<CompleteMultipartUploadError>>
+        + std::fmt::Display
+        + Send
+        + 'a,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new(
+        client: &'a C,
+        bucket: String,
+        key: String,
+        part_size: RangeInclusive<usize>,
+        concurrency_limit: Option<usize>,
+        retry: Option<Retry>,
+        part_timeout: Option<Duration>,
+        on_progress: Option<Arc<dyn Fn(UploadProgress) + Send + Sync>>,
+    ) -> Result<Self, E> {
+        let output = retry::with_retry(retry, || {
+            client.create_multipart_upload(CreateMultipartUploadRequest {
+                bucket: bucket.clone(),
+                key: key.clone(),
+                ..CreateMultipartUploadRequest::default()
+            })
+        })
+        .await?;
+        let upload_id = output.upload_id.unwrap();
+
+        Ok(Self {
+            client,
+            bucket,
+            key,
+            upload_id,
+            part_size,
+            concurrency_limit,
+            retry,
+            part_timeout,
+            on_progress,
+            buffer: BytesMut::new(),
+            next_part_number: 0,
+            in_flight: Vec::new(),
+            completed_parts: Vec::new(),
+            bytes_uploaded: 0,
+            parts_completed: 0,
+            phase: Phase::Active,
+            output: None,
+        })
+    }
+
+    /// Takes the result of the upload, available once `poll_shutdown` has completed.
+    pub fn take_output(&mut self) -> Option<Result<MultipartUploadOutput, E>> {
+        self.output.take().map(|result| result.map_err(|e| *e))
+    }
+
+    fn make_part_future(&mut self, body: Bytes) -> BoxFuture<'a, Result<(CompletedPart, usize), E>> {
+        self.next_part_number += 1;
+        let part_number = self.next_part_number;
+        let client = self.client;
+        let bucket = self.bucket.clone();
+        let key = self.key.clone();
+        let upload_id = self.upload_id.clone();
+        let retry = self.retry;
+        let part_timeout = self.part_timeout;
+        let content_length = body.len();
+        let content_md5 = md5::compute(&body).0;
+
+        Box::pin(
+            retry::with_retry(retry, move || {
+                let request = UploadPartRequest {
+                    body: Some(ByteStream::new(futures::stream::iter(std::iter::once(Ok(
+                        body.clone(),
+                    ))))),
+                    bucket: bucket.clone(),
+                    content_length: Some(content_length as _),
+                    content_md5: Some(base64::encode(content_md5)),
+                    key: key.clone(),
+                    part_number: part_number as _,
+                    upload_id: upload_id.clone(),
+                    ..UploadPartRequest::default()
+                };
+                with_part_timeout(part_timeout, client.upload_part(request))
+            })
+            .map_ok(move |output| {
+                (
+                    CompletedPart {
+                        e_tag: output.e_tag,
+                        part_number: Some(part_number as _),
+                    },
+                    content_length,
+                )
+            })
+            .err_into(),
+        )
+    }
+
+    fn drain_in_flight(&mut self, cx: &mut Context<'_>) -> Result<(), E> {
+        let completed_parts = &mut self.completed_parts;
+        let bytes_uploaded = &mut self.bytes_uploaded;
+        let parts_completed = &mut self.parts_completed;
+        let on_progress = &self.on_progress;
+        match poll_drain(&mut self.in_flight, cx, |(completed_part, part_bytes)| {
+            *bytes_uploaded += part_bytes;
+            *parts_completed += 1;
+            if let Some(on_progress) = on_progress {
+                on_progress(UploadProgress {
+                    part_number: completed_part.part_number.unwrap_or_default() as _,
+                    part_bytes,
+                    bytes_uploaded: *bytes_uploaded,
+                    parts_completed: *parts_completed,
+                });
+            }
+            completed_parts.push(completed_part);
+        }) {
+            Poll::Ready(Err(e)) => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn begin_abort(&mut self, error: E) {
+        let client = self.client;
+        let bucket = self.bucket.clone();
+        let key = self.key.clone();
+        let upload_id = self.upload_id.clone();
+        self.in_flight.clear();
+        self.phase = Phase::Aborting(
+            Box::pin(
+                client
+                    .abort_multipart_upload(AbortMultipartUploadRequest {
+                        bucket,
+                        key,
+                        upload_id,
+                        ..AbortMultipartUploadRequest::default()
+                    })
+                    .map(|_| ()),
+            ),
+            Box::new(error),
+        );
+    }
+}
+
+fn to_io_error<E: std::fmt::Display>(error: &E) -> io::Error {
+    io::Error::other(error.to_string())
+}
+
+impl<'a, C, E> AsyncWrite for MultipartUploadSink<'a, C, E>
+where
+    C: S3 + Sync,
+    E: From<RusotoError<CreateMultipartUploadError>>
+        + From<RusotoError<UploadPartError>>
+        + From<RusotoError<CompleteMultipartUploadError>>
+        + From<split::PartLimitExceeded>
+        + std::fmt::Display
+        + Send
+        + 'a,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        'outer: loop {
+            match &mut this.phase {
+                Phase::Aborting(fut, _) => {
+                    return match fut.as_mut().poll(cx) {
+                        Poll::Ready(()) => {
+                            let error = match std::mem::replace(&mut this.phase, Phase::Done) {
+                                Phase::Aborting(_, error) => error,
+                                _ => unreachable!(),
+                            };
+                            Poll::Ready(Err(to_io_error(&*error)))
+                        }
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+                Phase::ShuttingDown(_) | Phase::Done => {
+                    return Poll::Ready(Err(io::Error::other("write after shutdown")));
+                }
+                Phase::Active => {}
+            }
+
+            if let Err(e) = this.drain_in_flight(cx) {
+                this.begin_abort(e);
+                continue;
+            }
+
+            while this.buffer.len() >= *this.part_size.start() {
+                // The sink doesn't grow its part size adaptively like `split` does, so it has no
+                // way to keep shrinking part counts for arbitrarily long writers; guard the S3
+                // part-number cap directly instead, the same way `split` reports it.
+                if this.next_part_number >= split::MAX_PARTS {
+                    this.begin_abort(E::from(split::PartLimitExceeded));
+                    continue 'outer;
+                }
+                if this
+                    .concurrency_limit
+                    .is_some_and(|limit| this.in_flight.len() >= limit)
+                {
+                    // Every future already in `in_flight` was polled at least once by the
+                    // `drain_in_flight` call at the top of this function, *except* one just
+                    // pushed by an earlier iteration of this same loop: without draining again
+                    // here first, returning `Pending` while that future has never been polled
+                    // means nothing ever registers a waker for it, and the write hangs forever.
+                    if let Err(e) = this.drain_in_flight(cx) {
+                        this.begin_abort(e);
+                        continue 'outer;
+                    }
+                    if this
+                        .concurrency_limit
+                        .is_some_and(|limit| this.in_flight.len() >= limit)
+                    {
+                        return Poll::Pending;
+                    }
+                    continue;
+                }
+                let n = this.buffer.len().min(*this.part_size.end());
+                let body = this.buffer.split_to(n).freeze();
+                let fut = this.make_part_future(body);
+                this.in_flight.push(fut);
+            }
+
+            this.buffer.extend_from_slice(buf);
+            return Poll::Ready(Ok(buf.len()));
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.phase {
+                Phase::Aborting(fut, _) => {
+                    return match fut.as_mut().poll(cx) {
+                        Poll::Ready(()) => {
+                            let error = match std::mem::replace(&mut this.phase, Phase::Done) {
+                                Phase::Aborting(_, error) => error,
+                                _ => unreachable!(),
+                            };
+                            Poll::Ready(Err(to_io_error(&*error)))
+                        }
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+                Phase::ShuttingDown(_) | Phase::Done => return Poll::Ready(Ok(())),
+                Phase::Active => {}
+            }
+
+            if let Err(e) = this.drain_in_flight(cx) {
+                this.begin_abort(e);
+                continue;
+            }
+
+            return Poll::Ready(Ok(()));
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        'outer: loop {
+            match &mut this.phase {
+                Phase::Aborting(fut, _) => {
+                    return match fut.as_mut().poll(cx) {
+                        Poll::Ready(()) => {
+                            let error = match std::mem::replace(&mut this.phase, Phase::Done) {
+                                Phase::Aborting(_, error) => error,
+                                _ => unreachable!(),
+                            };
+                            this.output = Some(Err(error));
+                            Poll::Ready(Ok(()))
+                        }
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+                Phase::Done => return Poll::Ready(Ok(())),
+                Phase::ShuttingDown(fut) => {
+                    return match fut.as_mut().poll(cx) {
+                        Poll::Ready(result) => {
+                            let io_result = match &result {
+                                Ok(_) => Ok(()),
+                                Err(e) => Err(to_io_error(e)),
+                            };
+                            this.output = Some(result.map_err(Box::new));
+                            this.phase = Phase::Done;
+                            Poll::Ready(io_result)
+                        }
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+                Phase::Active => {}
+            }
+
+            if let Err(e) = this.drain_in_flight(cx) {
+                this.begin_abort(e);
+                continue;
+            }
+
+            let client = this.client;
+            let bucket = this.bucket.clone();
+            let key = this.key.clone();
+            let upload_id = this.upload_id.clone();
+            let retry = this.retry;
+            let on_progress = this.on_progress.clone();
+            let mut bytes_uploaded = this.bytes_uploaded;
+            let mut parts_completed = this.parts_completed;
+            let mut completed_parts = std::mem::take(&mut this.completed_parts);
+            let in_flight = std::mem::take(&mut this.in_flight);
+            // Mirror poll_write's `n = buffer.len().min(*part_size.end())` flush loop: a leftover
+            // buffer bigger than one part's max (e.g. from a single large `write` right before
+            // `shutdown`, which poll_write never gets a chance to flush) must still be split into
+            // part_size-capped parts rather than uploaded as one oversized UploadPartRequest.
+            let mut remaining = std::mem::take(&mut this.buffer);
+            let mut final_part_futures = Vec::new();
+            while !remaining.is_empty() {
+                if this.next_part_number >= split::MAX_PARTS {
+                    this.begin_abort(E::from(split::PartLimitExceeded));
+                    continue 'outer;
+                }
+                let n = remaining.len().min(*this.part_size.end());
+                let body = remaining.split_to(n).freeze();
+                final_part_futures.push(this.make_part_future(body));
+            }
+
+            let abort_bucket = bucket.clone();
+            let abort_key = key.clone();
+            let abort_upload_id = upload_id.clone();
+
+            this.phase = Phase::ShuttingDown(Box::pin(
+                (async move {
+                    let mut report = |completed_part: CompletedPart, part_bytes: usize| {
+                        bytes_uploaded += part_bytes;
+                        parts_completed += 1;
+                        if let Some(on_progress) = &on_progress {
+                            on_progress(UploadProgress {
+                                part_number: completed_part.part_number.unwrap_or_default() as _,
+                                part_bytes,
+                                bytes_uploaded,
+                                parts_completed,
+                            });
+                        }
+                        completed_parts.push(completed_part);
+                    };
+
+                    for (completed_part, part_bytes) in
+                        futures::future::try_join_all(final_part_futures).await?
+                    {
+                        report(completed_part, part_bytes);
+                    }
+                    for (completed_part, part_bytes) in
+                        futures::future::try_join_all(in_flight).await?
+                    {
+                        report(completed_part, part_bytes);
+                    }
+                    completed_parts.sort_by_key(|completed_part| completed_part.part_number);
+
+                    let output = retry::with_retry(retry, || {
+                        client.complete_multipart_upload(CompleteMultipartUploadRequest {
+                            bucket: bucket.clone(),
+                            key: key.clone(),
+                            multipart_upload: Some(CompletedMultipartUpload {
+                                parts: Some(completed_parts.clone()),
+                            }),
+                            upload_id: upload_id.clone(),
+                            ..CompleteMultipartUploadRequest::default()
+                        })
+                    })
+                    .await?;
+                    Ok(output)
+                })
+                .or_else(move |e: E| {
+                    client
+                        .abort_multipart_upload(AbortMultipartUploadRequest {
+                            bucket: abort_bucket,
+                            key: abort_key,
+                            upload_id: abort_upload_id,
+                            ..AbortMultipartUploadRequest::default()
+                        })
+                        .map(move |_| Err(e))
+                }),
+            ));
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use bytes::Bytes;
+use futures::Stream;
+use std::ops::RangeInclusive;
+use std::pin::Pin;
+
+// https://docs.aws.amazon.com/AmazonS3/latest/userguide/qfacts.html
+pub(crate) const MAX_PARTS: usize = 10_000;
+
+#[derive(Clone, Debug)]
+pub struct Part {
+    pub part_number: usize,
+    pub body: Vec<Bytes>,
+    pub content_length: usize,
+    pub content_md5: [u8; 16],
+}
+
+/// Even the largest allowed part size (`*part_size.end()`) could not keep a stream's part count
+/// under the S3-imposed [`MAX_PARTS`] limit.
+#[derive(Debug)]
+pub struct PartLimitExceeded;
+
+impl std::fmt::Display for PartLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "multipart upload would exceed the {}-part limit even at the maximum part size",
+            MAX_PARTS
+        )
+    }
+}
+
+impl std::error::Error for PartLimitExceeded {}
+
+struct State<B> {
+    body: Pin<Box<B>>,
+    part_size: RangeInclusive<usize>,
+    current_part_size: usize,
+    part_number: usize,
+    skip_remaining: usize,
+    pending: Option<Bytes>,
+    done: bool,
+    adaptive: bool,
+}
+
+/// Splits `body` into parts of roughly `part_size` each, numbered starting at
+/// `start_part_number + 1`, after discarding the first `skip_bytes` bytes of `body` unread. This
+/// lets [`crate::resume`] re-drive a stream from the byte offset of the first not-yet-uploaded
+/// part rather than re-uploading parts S3 already has.
+///
+/// When `adaptive` is set, the target part size starts at `start_part_size` (clamped to
+/// `part_size`) and doubles (capped at `*part_size.end()`) every time `part_number` crosses a
+/// power-of-two boundary. Starting from 5 MiB with `part_size` = [`crate::PART_SIZE`], the size
+/// reaches 5 GiB at part 513 and the S3 [`MAX_PARTS`] cap is reached at about 47 TiB; a smaller
+/// `*part_size.end()` lowers that ceiling accordingly. A resumed upload should pass the largest
+/// part size S3 already reports, not `*part_size.start()`: seeding back at the floor would let
+/// `part_number` run far past the point where `current_part_size` would naturally have grown,
+/// silently reintroducing the part-count blowup `adaptive` exists to prevent. If the stream is so
+/// large that even the maximum part size would exceed the cap, the stream yields a
+/// [`PartLimitExceeded`] error instead of producing an invalid upload.
+#[allow(clippy::too_many_arguments)]
+pub fn split<B, E>(
+    body: B,
+    part_size: RangeInclusive<usize>,
+    adaptive: bool,
+    start_part_number: usize,
+    skip_bytes: usize,
+    start_part_size: usize,
+) -> impl Stream<Item = Result<Part, E>>
+where
+    B: Stream<Item = Result<Bytes, E>>,
+    E: From<PartLimitExceeded>,
+{
+    let current_part_size = start_part_size.clamp(*part_size.start(), *part_size.end());
+
+    futures::stream::try_unfold(
+        State {
+            body: Box::pin(body),
+            part_size,
+            current_part_size,
+            part_number: start_part_number,
+            skip_remaining: skip_bytes,
+            pending: None,
+            done: false,
+            adaptive,
+        },
+        |mut state| async move {
+            if state.done {
+                return Ok(None);
+            }
+
+            while state.skip_remaining > 0 {
+                match futures::StreamExt::next(&mut state.body).await {
+                    Some(Ok(mut bytes)) => {
+                        if bytes.len() > state.skip_remaining {
+                            let kept = bytes.split_off(state.skip_remaining);
+                            state.skip_remaining = 0;
+                            state.pending = Some(kept);
+                        } else {
+                            state.skip_remaining -= bytes.len();
+                        }
+                    }
+                    Some(Err(e)) => return Err(e),
+                    None => return Ok(None),
+                }
+            }
+
+            let target = if state.adaptive {
+                state.current_part_size
+            } else {
+                *state.part_size.start()
+            };
+            let max = if state.adaptive {
+                state.current_part_size
+            } else {
+                *state.part_size.end()
+            };
+
+            if state.adaptive
+                && state.part_number >= MAX_PARTS
+                && state.current_part_size >= *state.part_size.end()
+            {
+                return Err(E::from(PartLimitExceeded));
+            }
+
+            let mut chunks = Vec::new();
+            let mut content_length = 0;
+            let mut ctx = md5::Context::new();
+
+            if let Some(mut bytes) = state.pending.take() {
+                if bytes.len() > max {
+                    state.pending = Some(bytes.split_off(max));
+                }
+                ctx.consume(&bytes);
+                content_length += bytes.len();
+                chunks.push(bytes);
+            }
+
+            while content_length < target {
+                match futures::StreamExt::next(&mut state.body).await {
+                    Some(Ok(mut bytes)) => {
+                        let remaining = max - content_length;
+                        if bytes.len() > remaining {
+                            state.pending = Some(bytes.split_off(remaining));
+                        }
+                        ctx.consume(&bytes);
+                        content_length += bytes.len();
+                        chunks.push(bytes);
+                    }
+                    Some(Err(e)) => return Err(e),
+                    None => {
+                        state.done = true;
+                        break;
+                    }
+                }
+            }
+
+            if chunks.is_empty() {
+                return Ok(None);
+            }
+
+            if state.adaptive
+                && state.part_number >= MAX_PARTS
+                && state.current_part_size >= *state.part_size.end()
+            {
+                return Err(E::from(PartLimitExceeded));
+            }
+
+            state.part_number += 1;
+            if state.adaptive && state.part_number.is_power_of_two() {
+                state.current_part_size =
+                    (state.current_part_size * 2).min(*state.part_size.end());
+            }
+
+            Ok(Some((
+                Part {
+                    part_number: state.part_number,
+                    body: chunks,
+                    content_length,
+                    content_md5: ctx.compute().into(),
+                },
+                state,
+            )))
+        },
+    )
+}
@@ -0,0 +1,801 @@
+//! A minimal `S3` stub for [`crate::sink::MultipartUploadSink`] and [`crate::resume`] tests: only
+//! the multipart-upload methods the crate actually calls are implemented. Every other method
+//! panics if exercised, which would mean the code under test started relying on an S3 API this
+//! crate doesn't use.
+
+use async_trait::async_trait;
+use rusoto_core::{HttpDispatchError, RusotoError};
+use rusoto_s3::*;
+use std::sync::Mutex;
+
+pub struct FakeS3 {
+    upload_id: String,
+    fail_upload_part_at: Option<usize>,
+    upload_part_calls: Mutex<Vec<i64>>,
+    aborted: Mutex<bool>,
+    existing_parts: Vec<Part>,
+    list_parts_page_size: usize,
+    completed_parts: Mutex<Option<Vec<CompletedPart>>>,
+}
+
+impl FakeS3 {
+    pub fn new() -> Self {
+        Self {
+            upload_id: "fake-upload-id".to_string(),
+            fail_upload_part_at: None,
+            upload_part_calls: Mutex::new(Vec::new()),
+            aborted: Mutex::new(false),
+            existing_parts: Vec::new(),
+            list_parts_page_size: usize::MAX,
+            completed_parts: Mutex::new(None),
+        }
+    }
+
+    /// Fails the `n`th call (1-indexed) to `upload_part` with a dispatch error.
+    pub fn failing_at(n: usize) -> Self {
+        Self {
+            fail_upload_part_at: Some(n),
+            ..Self::new()
+        }
+    }
+
+    /// Seeds `list_parts` with parts a previous, interrupted upload already has, paginated
+    /// `page_size` parts at a time so `resume`'s `part_number_marker` loop has more than one page
+    /// to walk.
+    pub fn with_existing_parts(parts: Vec<Part>, page_size: usize) -> Self {
+        Self {
+            existing_parts: parts,
+            list_parts_page_size: page_size,
+            ..Self::new()
+        }
+    }
+
+    pub fn upload_part_calls(&self) -> Vec<i64> {
+        self.upload_part_calls.lock().unwrap().clone()
+    }
+
+    pub fn was_aborted(&self) -> bool {
+        *self.aborted.lock().unwrap()
+    }
+
+    /// The `parts` sent to `complete_multipart_upload`, or `None` if it was never called.
+    pub fn completed_parts(&self) -> Option<Vec<CompletedPart>> {
+        self.completed_parts.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl S3 for FakeS3 {
+    async fn create_multipart_upload(
+        &self,
+        _input: CreateMultipartUploadRequest,
+    ) -> Result<CreateMultipartUploadOutput, RusotoError<CreateMultipartUploadError>> {
+        Ok(CreateMultipartUploadOutput {
+            upload_id: Some(self.upload_id.clone()),
+            ..CreateMultipartUploadOutput::default()
+        })
+    }
+
+    async fn upload_part(
+        &self,
+        input: UploadPartRequest,
+    ) -> Result<UploadPartOutput, RusotoError<UploadPartError>> {
+        let call_number = {
+            let mut calls = self.upload_part_calls.lock().unwrap();
+            calls.push(input.part_number);
+            calls.len()
+        };
+
+        if self.fail_upload_part_at == Some(call_number) {
+            return Err(RusotoError::HttpDispatch(HttpDispatchError::new(
+                "upload_part failed".to_string(),
+            )));
+        }
+
+        Ok(UploadPartOutput {
+            e_tag: Some(format!("etag-{}", input.part_number)),
+            ..UploadPartOutput::default()
+        })
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        input: CompleteMultipartUploadRequest,
+    ) -> Result<CompleteMultipartUploadOutput, RusotoError<CompleteMultipartUploadError>> {
+        *self.completed_parts.lock().unwrap() =
+            Some(input.multipart_upload.and_then(|u| u.parts).unwrap_or_default());
+        Ok(CompleteMultipartUploadOutput::default())
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        _input: AbortMultipartUploadRequest,
+    ) -> Result<AbortMultipartUploadOutput, RusotoError<AbortMultipartUploadError>> {
+        *self.aborted.lock().unwrap() = true;
+        Ok(AbortMultipartUploadOutput::default())
+    }
+
+    async fn copy_object(
+        &self,
+        _input: CopyObjectRequest,
+    ) -> Result<CopyObjectOutput, RusotoError<CopyObjectError>> {
+        unimplemented!("FakeS3::copy_object is not exercised by these tests")
+    }
+
+    async fn create_bucket(
+        &self,
+        _input: CreateBucketRequest,
+    ) -> Result<CreateBucketOutput, RusotoError<CreateBucketError>> {
+        unimplemented!("FakeS3::create_bucket is not exercised by these tests")
+    }
+
+    async fn delete_bucket(
+        &self,
+        _input: DeleteBucketRequest,
+    ) -> Result<(), RusotoError<DeleteBucketError>> {
+        unimplemented!("FakeS3::delete_bucket is not exercised by these tests")
+    }
+
+    async fn delete_bucket_analytics_configuration(
+        &self,
+        _input: DeleteBucketAnalyticsConfigurationRequest,
+    ) -> Result<(), RusotoError<DeleteBucketAnalyticsConfigurationError>> {
+        unimplemented!("FakeS3::delete_bucket_analytics_configuration is not exercised by these tests")
+    }
+
+    async fn delete_bucket_cors(
+        &self,
+        _input: DeleteBucketCorsRequest,
+    ) -> Result<(), RusotoError<DeleteBucketCorsError>> {
+        unimplemented!("FakeS3::delete_bucket_cors is not exercised by these tests")
+    }
+
+    async fn delete_bucket_encryption(
+        &self,
+        _input: DeleteBucketEncryptionRequest,
+    ) -> Result<(), RusotoError<DeleteBucketEncryptionError>> {
+        unimplemented!("FakeS3::delete_bucket_encryption is not exercised by these tests")
+    }
+
+    async fn delete_bucket_intelligent_tiering_configuration(
+        &self,
+        _input: DeleteBucketIntelligentTieringConfigurationRequest,
+    ) -> Result<(), RusotoError<DeleteBucketIntelligentTieringConfigurationError>> {
+        unimplemented!(
+            "FakeS3::delete_bucket_intelligent_tiering_configuration is not exercised by these tests"
+        )
+    }
+
+    async fn delete_bucket_inventory_configuration(
+        &self,
+        _input: DeleteBucketInventoryConfigurationRequest,
+    ) -> Result<(), RusotoError<DeleteBucketInventoryConfigurationError>> {
+        unimplemented!("FakeS3::delete_bucket_inventory_configuration is not exercised by these tests")
+    }
+
+    async fn delete_bucket_lifecycle(
+        &self,
+        _input: DeleteBucketLifecycleRequest,
+    ) -> Result<(), RusotoError<DeleteBucketLifecycleError>> {
+        unimplemented!("FakeS3::delete_bucket_lifecycle is not exercised by these tests")
+    }
+
+    async fn delete_bucket_metrics_configuration(
+        &self,
+        _input: DeleteBucketMetricsConfigurationRequest,
+    ) -> Result<(), RusotoError<DeleteBucketMetricsConfigurationError>> {
+        unimplemented!("FakeS3::delete_bucket_metrics_configuration is not exercised by these tests")
+    }
+
+    async fn delete_bucket_ownership_controls(
+        &self,
+        _input: DeleteBucketOwnershipControlsRequest,
+    ) -> Result<(), RusotoError<DeleteBucketOwnershipControlsError>> {
+        unimplemented!("FakeS3::delete_bucket_ownership_controls is not exercised by these tests")
+    }
+
+    async fn delete_bucket_policy(
+        &self,
+        _input: DeleteBucketPolicyRequest,
+    ) -> Result<(), RusotoError<DeleteBucketPolicyError>> {
+        unimplemented!("FakeS3::delete_bucket_policy is not exercised by these tests")
+    }
+
+    async fn delete_bucket_replication(
+        &self,
+        _input: DeleteBucketReplicationRequest,
+    ) -> Result<(), RusotoError<DeleteBucketReplicationError>> {
+        unimplemented!("FakeS3::delete_bucket_replication is not exercised by these tests")
+    }
+
+    async fn delete_bucket_tagging(
+        &self,
+        _input: DeleteBucketTaggingRequest,
+    ) -> Result<(), RusotoError<DeleteBucketTaggingError>> {
+        unimplemented!("FakeS3::delete_bucket_tagging is not exercised by these tests")
+    }
+
+    async fn delete_bucket_website(
+        &self,
+        _input: DeleteBucketWebsiteRequest,
+    ) -> Result<(), RusotoError<DeleteBucketWebsiteError>> {
+        unimplemented!("FakeS3::delete_bucket_website is not exercised by these tests")
+    }
+
+    async fn delete_object(
+        &self,
+        _input: DeleteObjectRequest,
+    ) -> Result<DeleteObjectOutput, RusotoError<DeleteObjectError>> {
+        unimplemented!("FakeS3::delete_object is not exercised by these tests")
+    }
+
+    async fn delete_object_tagging(
+        &self,
+        _input: DeleteObjectTaggingRequest,
+    ) -> Result<DeleteObjectTaggingOutput, RusotoError<DeleteObjectTaggingError>> {
+        unimplemented!("FakeS3::delete_object_tagging is not exercised by these tests")
+    }
+
+    async fn delete_objects(
+        &self,
+        _input: DeleteObjectsRequest,
+    ) -> Result<DeleteObjectsOutput, RusotoError<DeleteObjectsError>> {
+        unimplemented!("FakeS3::delete_objects is not exercised by these tests")
+    }
+
+    async fn delete_public_access_block(
+        &self,
+        _input: DeletePublicAccessBlockRequest,
+    ) -> Result<(), RusotoError<DeletePublicAccessBlockError>> {
+        unimplemented!("FakeS3::delete_public_access_block is not exercised by these tests")
+    }
+
+    async fn get_bucket_accelerate_configuration(
+        &self,
+        _input: GetBucketAccelerateConfigurationRequest,
+    ) -> Result<GetBucketAccelerateConfigurationOutput, RusotoError<GetBucketAccelerateConfigurationError>>
+    {
+        unimplemented!("FakeS3::get_bucket_accelerate_configuration is not exercised by these tests")
+    }
+
+    async fn get_bucket_acl(
+        &self,
+        _input: GetBucketAclRequest,
+    ) -> Result<GetBucketAclOutput, RusotoError<GetBucketAclError>> {
+        unimplemented!("FakeS3::get_bucket_acl is not exercised by these tests")
+    }
+
+    async fn get_bucket_analytics_configuration(
+        &self,
+        _input: GetBucketAnalyticsConfigurationRequest,
+    ) -> Result<GetBucketAnalyticsConfigurationOutput, RusotoError<GetBucketAnalyticsConfigurationError>>
+    {
+        unimplemented!("FakeS3::get_bucket_analytics_configuration is not exercised by these tests")
+    }
+
+    async fn get_bucket_cors(
+        &self,
+        _input: GetBucketCorsRequest,
+    ) -> Result<GetBucketCorsOutput, RusotoError<GetBucketCorsError>> {
+        unimplemented!("FakeS3::get_bucket_cors is not exercised by these tests")
+    }
+
+    async fn get_bucket_encryption(
+        &self,
+        _input: GetBucketEncryptionRequest,
+    ) -> Result<GetBucketEncryptionOutput, RusotoError<GetBucketEncryptionError>> {
+        unimplemented!("FakeS3::get_bucket_encryption is not exercised by these tests")
+    }
+
+    async fn get_bucket_intelligent_tiering_configuration(
+        &self,
+        _input: GetBucketIntelligentTieringConfigurationRequest,
+    ) -> Result<
+        GetBucketIntelligentTieringConfigurationOutput,
+        RusotoError<GetBucketIntelligentTieringConfigurationError>,
+    > {
+        unimplemented!(
+            "FakeS3::get_bucket_intelligent_tiering_configuration is not exercised by these tests"
+        )
+    }
+
+    async fn get_bucket_inventory_configuration(
+        &self,
+        _input: GetBucketInventoryConfigurationRequest,
+    ) -> Result<GetBucketInventoryConfigurationOutput, RusotoError<GetBucketInventoryConfigurationError>>
+    {
+        unimplemented!("FakeS3::get_bucket_inventory_configuration is not exercised by these tests")
+    }
+
+    async fn get_bucket_lifecycle(
+        &self,
+        _input: GetBucketLifecycleRequest,
+    ) -> Result<GetBucketLifecycleOutput, RusotoError<GetBucketLifecycleError>> {
+        unimplemented!("FakeS3::get_bucket_lifecycle is not exercised by these tests")
+    }
+
+    async fn get_bucket_lifecycle_configuration(
+        &self,
+        _input: GetBucketLifecycleConfigurationRequest,
+    ) -> Result<GetBucketLifecycleConfigurationOutput, RusotoError<GetBucketLifecycleConfigurationError>>
+    {
+        unimplemented!("FakeS3::get_bucket_lifecycle_configuration is not exercised by these tests")
+    }
+
+    async fn get_bucket_location(
+        &self,
+        _input: GetBucketLocationRequest,
+    ) -> Result<GetBucketLocationOutput, RusotoError<GetBucketLocationError>> {
+        unimplemented!("FakeS3::get_bucket_location is not exercised by these tests")
+    }
+
+    async fn get_bucket_logging(
+        &self,
+        _input: GetBucketLoggingRequest,
+    ) -> Result<GetBucketLoggingOutput, RusotoError<GetBucketLoggingError>> {
+        unimplemented!("FakeS3::get_bucket_logging is not exercised by these tests")
+    }
+
+    async fn get_bucket_metrics_configuration(
+        &self,
+        _input: GetBucketMetricsConfigurationRequest,
+    ) -> Result<GetBucketMetricsConfigurationOutput, RusotoError<GetBucketMetricsConfigurationError>> {
+        unimplemented!("FakeS3::get_bucket_metrics_configuration is not exercised by these tests")
+    }
+
+    async fn get_bucket_notification(
+        &self,
+        _input: GetBucketNotificationConfigurationRequest,
+    ) -> Result<NotificationConfigurationDeprecated, RusotoError<GetBucketNotificationError>> {
+        unimplemented!("FakeS3::get_bucket_notification is not exercised by these tests")
+    }
+
+    async fn get_bucket_notification_configuration(
+        &self,
+        _input: GetBucketNotificationConfigurationRequest,
+    ) -> Result<NotificationConfiguration, RusotoError<GetBucketNotificationConfigurationError>> {
+        unimplemented!("FakeS3::get_bucket_notification_configuration is not exercised by these tests")
+    }
+
+    async fn get_bucket_ownership_controls(
+        &self,
+        _input: GetBucketOwnershipControlsRequest,
+    ) -> Result<GetBucketOwnershipControlsOutput, RusotoError<GetBucketOwnershipControlsError>> {
+        unimplemented!("FakeS3::get_bucket_ownership_controls is not exercised by these tests")
+    }
+
+    async fn get_bucket_policy(
+        &self,
+        _input: GetBucketPolicyRequest,
+    ) -> Result<GetBucketPolicyOutput, RusotoError<GetBucketPolicyError>> {
+        unimplemented!("FakeS3::get_bucket_policy is not exercised by these tests")
+    }
+
+    async fn get_bucket_policy_status(
+        &self,
+        _input: GetBucketPolicyStatusRequest,
+    ) -> Result<GetBucketPolicyStatusOutput, RusotoError<GetBucketPolicyStatusError>> {
+        unimplemented!("FakeS3::get_bucket_policy_status is not exercised by these tests")
+    }
+
+    async fn get_bucket_replication(
+        &self,
+        _input: GetBucketReplicationRequest,
+    ) -> Result<GetBucketReplicationOutput, RusotoError<GetBucketReplicationError>> {
+        unimplemented!("FakeS3::get_bucket_replication is not exercised by these tests")
+    }
+
+    async fn get_bucket_request_payment(
+        &self,
+        _input: GetBucketRequestPaymentRequest,
+    ) -> Result<GetBucketRequestPaymentOutput, RusotoError<GetBucketRequestPaymentError>> {
+        unimplemented!("FakeS3::get_bucket_request_payment is not exercised by these tests")
+    }
+
+    async fn get_bucket_tagging(
+        &self,
+        _input: GetBucketTaggingRequest,
+    ) -> Result<GetBucketTaggingOutput, RusotoError<GetBucketTaggingError>> {
+        unimplemented!("FakeS3::get_bucket_tagging is not exercised by these tests")
+    }
+
+    async fn get_bucket_versioning(
+        &self,
+        _input: GetBucketVersioningRequest,
+    ) -> Result<GetBucketVersioningOutput, RusotoError<GetBucketVersioningError>> {
+        unimplemented!("FakeS3::get_bucket_versioning is not exercised by these tests")
+    }
+
+    async fn get_bucket_website(
+        &self,
+        _input: GetBucketWebsiteRequest,
+    ) -> Result<GetBucketWebsiteOutput, RusotoError<GetBucketWebsiteError>> {
+        unimplemented!("FakeS3::get_bucket_website is not exercised by these tests")
+    }
+
+    async fn get_object(
+        &self,
+        _input: GetObjectRequest,
+    ) -> Result<GetObjectOutput, RusotoError<GetObjectError>> {
+        unimplemented!("FakeS3::get_object is not exercised by these tests")
+    }
+
+    async fn get_object_acl(
+        &self,
+        _input: GetObjectAclRequest,
+    ) -> Result<GetObjectAclOutput, RusotoError<GetObjectAclError>> {
+        unimplemented!("FakeS3::get_object_acl is not exercised by these tests")
+    }
+
+    async fn get_object_legal_hold(
+        &self,
+        _input: GetObjectLegalHoldRequest,
+    ) -> Result<GetObjectLegalHoldOutput, RusotoError<GetObjectLegalHoldError>> {
+        unimplemented!("FakeS3::get_object_legal_hold is not exercised by these tests")
+    }
+
+    async fn get_object_lock_configuration(
+        &self,
+        _input: GetObjectLockConfigurationRequest,
+    ) -> Result<GetObjectLockConfigurationOutput, RusotoError<GetObjectLockConfigurationError>> {
+        unimplemented!("FakeS3::get_object_lock_configuration is not exercised by these tests")
+    }
+
+    async fn get_object_retention(
+        &self,
+        _input: GetObjectRetentionRequest,
+    ) -> Result<GetObjectRetentionOutput, RusotoError<GetObjectRetentionError>> {
+        unimplemented!("FakeS3::get_object_retention is not exercised by these tests")
+    }
+
+    async fn get_object_tagging(
+        &self,
+        _input: GetObjectTaggingRequest,
+    ) -> Result<GetObjectTaggingOutput, RusotoError<GetObjectTaggingError>> {
+        unimplemented!("FakeS3::get_object_tagging is not exercised by these tests")
+    }
+
+    async fn get_object_torrent(
+        &self,
+        _input: GetObjectTorrentRequest,
+    ) -> Result<GetObjectTorrentOutput, RusotoError<GetObjectTorrentError>> {
+        unimplemented!("FakeS3::get_object_torrent is not exercised by these tests")
+    }
+
+    async fn get_public_access_block(
+        &self,
+        _input: GetPublicAccessBlockRequest,
+    ) -> Result<GetPublicAccessBlockOutput, RusotoError<GetPublicAccessBlockError>> {
+        unimplemented!("FakeS3::get_public_access_block is not exercised by these tests")
+    }
+
+    async fn head_bucket(
+        &self,
+        _input: HeadBucketRequest,
+    ) -> Result<(), RusotoError<HeadBucketError>> {
+        unimplemented!("FakeS3::head_bucket is not exercised by these tests")
+    }
+
+    async fn head_object(
+        &self,
+        _input: HeadObjectRequest,
+    ) -> Result<HeadObjectOutput, RusotoError<HeadObjectError>> {
+        unimplemented!("FakeS3::head_object is not exercised by these tests")
+    }
+
+    async fn list_bucket_analytics_configurations(
+        &self,
+        _input: ListBucketAnalyticsConfigurationsRequest,
+    ) -> Result<
+        ListBucketAnalyticsConfigurationsOutput,
+        RusotoError<ListBucketAnalyticsConfigurationsError>,
+    > {
+        unimplemented!("FakeS3::list_bucket_analytics_configurations is not exercised by these tests")
+    }
+
+    async fn list_bucket_intelligent_tiering_configurations(
+        &self,
+        _input: ListBucketIntelligentTieringConfigurationsRequest,
+    ) -> Result<
+        ListBucketIntelligentTieringConfigurationsOutput,
+        RusotoError<ListBucketIntelligentTieringConfigurationsError>,
+    > {
+        unimplemented!(
+            "FakeS3::list_bucket_intelligent_tiering_configurations is not exercised by these tests"
+        )
+    }
+
+    async fn list_bucket_inventory_configurations(
+        &self,
+        _input: ListBucketInventoryConfigurationsRequest,
+    ) -> Result<
+        ListBucketInventoryConfigurationsOutput,
+        RusotoError<ListBucketInventoryConfigurationsError>,
+    > {
+        unimplemented!("FakeS3::list_bucket_inventory_configurations is not exercised by these tests")
+    }
+
+    async fn list_bucket_metrics_configurations(
+        &self,
+        _input: ListBucketMetricsConfigurationsRequest,
+    ) -> Result<
+        ListBucketMetricsConfigurationsOutput,
+        RusotoError<ListBucketMetricsConfigurationsError>,
+    > {
+        unimplemented!("FakeS3::list_bucket_metrics_configurations is not exercised by these tests")
+    }
+
+    async fn list_buckets(&self) -> Result<ListBucketsOutput, RusotoError<ListBucketsError>> {
+        unimplemented!("FakeS3::list_buckets is not exercised by these tests")
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        _input: ListMultipartUploadsRequest,
+    ) -> Result<ListMultipartUploadsOutput, RusotoError<ListMultipartUploadsError>> {
+        unimplemented!("FakeS3::list_multipart_uploads is not exercised by these tests")
+    }
+
+    async fn list_object_versions(
+        &self,
+        _input: ListObjectVersionsRequest,
+    ) -> Result<ListObjectVersionsOutput, RusotoError<ListObjectVersionsError>> {
+        unimplemented!("FakeS3::list_object_versions is not exercised by these tests")
+    }
+
+    async fn list_objects(
+        &self,
+        _input: ListObjectsRequest,
+    ) -> Result<ListObjectsOutput, RusotoError<ListObjectsError>> {
+        unimplemented!("FakeS3::list_objects is not exercised by these tests")
+    }
+
+    async fn list_objects_v2(
+        &self,
+        _input: ListObjectsV2Request,
+    ) -> Result<ListObjectsV2Output, RusotoError<ListObjectsV2Error>> {
+        unimplemented!("FakeS3::list_objects_v2 is not exercised by these tests")
+    }
+
+    async fn list_parts(
+        &self,
+        input: ListPartsRequest,
+    ) -> Result<ListPartsOutput, RusotoError<ListPartsError>> {
+        let marker = input.part_number_marker.unwrap_or(0);
+        let mut page: Vec<Part> = self
+            .existing_parts
+            .iter()
+            .filter(|part| part.part_number.unwrap_or(0) > marker)
+            .cloned()
+            .collect();
+        page.sort_by_key(|part| part.part_number);
+        let is_truncated = page.len() > self.list_parts_page_size;
+        page.truncate(self.list_parts_page_size);
+
+        Ok(ListPartsOutput {
+            is_truncated: Some(is_truncated),
+            next_part_number_marker: page.last().and_then(|part| part.part_number),
+            parts: Some(page),
+            ..ListPartsOutput::default()
+        })
+    }
+
+    async fn put_bucket_accelerate_configuration(
+        &self,
+        _input: PutBucketAccelerateConfigurationRequest,
+    ) -> Result<(), RusotoError<PutBucketAccelerateConfigurationError>> {
+        unimplemented!("FakeS3::put_bucket_accelerate_configuration is not exercised by these tests")
+    }
+
+    async fn put_bucket_acl(
+        &self,
+        _input: PutBucketAclRequest,
+    ) -> Result<(), RusotoError<PutBucketAclError>> {
+        unimplemented!("FakeS3::put_bucket_acl is not exercised by these tests")
+    }
+
+    async fn put_bucket_analytics_configuration(
+        &self,
+        _input: PutBucketAnalyticsConfigurationRequest,
+    ) -> Result<(), RusotoError<PutBucketAnalyticsConfigurationError>> {
+        unimplemented!("FakeS3::put_bucket_analytics_configuration is not exercised by these tests")
+    }
+
+    async fn put_bucket_cors(
+        &self,
+        _input: PutBucketCorsRequest,
+    ) -> Result<(), RusotoError<PutBucketCorsError>> {
+        unimplemented!("FakeS3::put_bucket_cors is not exercised by these tests")
+    }
+
+    async fn put_bucket_encryption(
+        &self,
+        _input: PutBucketEncryptionRequest,
+    ) -> Result<(), RusotoError<PutBucketEncryptionError>> {
+        unimplemented!("FakeS3::put_bucket_encryption is not exercised by these tests")
+    }
+
+    async fn put_bucket_intelligent_tiering_configuration(
+        &self,
+        _input: PutBucketIntelligentTieringConfigurationRequest,
+    ) -> Result<(), RusotoError<PutBucketIntelligentTieringConfigurationError>> {
+        unimplemented!(
+            "FakeS3::put_bucket_intelligent_tiering_configuration is not exercised by these tests"
+        )
+    }
+
+    async fn put_bucket_inventory_configuration(
+        &self,
+        _input: PutBucketInventoryConfigurationRequest,
+    ) -> Result<(), RusotoError<PutBucketInventoryConfigurationError>> {
+        unimplemented!("FakeS3::put_bucket_inventory_configuration is not exercised by these tests")
+    }
+
+    async fn put_bucket_lifecycle(
+        &self,
+        _input: PutBucketLifecycleRequest,
+    ) -> Result<(), RusotoError<PutBucketLifecycleError>> {
+        unimplemented!("FakeS3::put_bucket_lifecycle is not exercised by these tests")
+    }
+
+    async fn put_bucket_lifecycle_configuration(
+        &self,
+        _input: PutBucketLifecycleConfigurationRequest,
+    ) -> Result<(), RusotoError<PutBucketLifecycleConfigurationError>> {
+        unimplemented!("FakeS3::put_bucket_lifecycle_configuration is not exercised by these tests")
+    }
+
+    async fn put_bucket_logging(
+        &self,
+        _input: PutBucketLoggingRequest,
+    ) -> Result<(), RusotoError<PutBucketLoggingError>> {
+        unimplemented!("FakeS3::put_bucket_logging is not exercised by these tests")
+    }
+
+    async fn put_bucket_metrics_configuration(
+        &self,
+        _input: PutBucketMetricsConfigurationRequest,
+    ) -> Result<(), RusotoError<PutBucketMetricsConfigurationError>> {
+        unimplemented!("FakeS3::put_bucket_metrics_configuration is not exercised by these tests")
+    }
+
+    async fn put_bucket_notification(
+        &self,
+        _input: PutBucketNotificationRequest,
+    ) -> Result<(), RusotoError<PutBucketNotificationError>> {
+        unimplemented!("FakeS3::put_bucket_notification is not exercised by these tests")
+    }
+
+    async fn put_bucket_notification_configuration(
+        &self,
+        _input: PutBucketNotificationConfigurationRequest,
+    ) -> Result<(), RusotoError<PutBucketNotificationConfigurationError>> {
+        unimplemented!("FakeS3::put_bucket_notification_configuration is not exercised by these tests")
+    }
+
+    async fn put_bucket_ownership_controls(
+        &self,
+        _input: PutBucketOwnershipControlsRequest,
+    ) -> Result<(), RusotoError<PutBucketOwnershipControlsError>> {
+        unimplemented!("FakeS3::put_bucket_ownership_controls is not exercised by these tests")
+    }
+
+    async fn put_bucket_policy(
+        &self,
+        _input: PutBucketPolicyRequest,
+    ) -> Result<(), RusotoError<PutBucketPolicyError>> {
+        unimplemented!("FakeS3::put_bucket_policy is not exercised by these tests")
+    }
+
+    async fn put_bucket_replication(
+        &self,
+        _input: PutBucketReplicationRequest,
+    ) -> Result<(), RusotoError<PutBucketReplicationError>> {
+        unimplemented!("FakeS3::put_bucket_replication is not exercised by these tests")
+    }
+
+    async fn put_bucket_request_payment(
+        &self,
+        _input: PutBucketRequestPaymentRequest,
+    ) -> Result<(), RusotoError<PutBucketRequestPaymentError>> {
+        unimplemented!("FakeS3::put_bucket_request_payment is not exercised by these tests")
+    }
+
+    async fn put_bucket_tagging(
+        &self,
+        _input: PutBucketTaggingRequest,
+    ) -> Result<(), RusotoError<PutBucketTaggingError>> {
+        unimplemented!("FakeS3::put_bucket_tagging is not exercised by these tests")
+    }
+
+    async fn put_bucket_versioning(
+        &self,
+        _input: PutBucketVersioningRequest,
+    ) -> Result<(), RusotoError<PutBucketVersioningError>> {
+        unimplemented!("FakeS3::put_bucket_versioning is not exercised by these tests")
+    }
+
+    async fn put_bucket_website(
+        &self,
+        _input: PutBucketWebsiteRequest,
+    ) -> Result<(), RusotoError<PutBucketWebsiteError>> {
+        unimplemented!("FakeS3::put_bucket_website is not exercised by these tests")
+    }
+
+    async fn put_object(
+        &self,
+        _input: PutObjectRequest,
+    ) -> Result<PutObjectOutput, RusotoError<PutObjectError>> {
+        unimplemented!("FakeS3::put_object is not exercised by these tests")
+    }
+
+    async fn put_object_acl(
+        &self,
+        _input: PutObjectAclRequest,
+    ) -> Result<PutObjectAclOutput, RusotoError<PutObjectAclError>> {
+        unimplemented!("FakeS3::put_object_acl is not exercised by these tests")
+    }
+
+    async fn put_object_legal_hold(
+        &self,
+        _input: PutObjectLegalHoldRequest,
+    ) -> Result<PutObjectLegalHoldOutput, RusotoError<PutObjectLegalHoldError>> {
+        unimplemented!("FakeS3::put_object_legal_hold is not exercised by these tests")
+    }
+
+    async fn put_object_lock_configuration(
+        &self,
+        _input: PutObjectLockConfigurationRequest,
+    ) -> Result<PutObjectLockConfigurationOutput, RusotoError<PutObjectLockConfigurationError>> {
+        unimplemented!("FakeS3::put_object_lock_configuration is not exercised by these tests")
+    }
+
+    async fn put_object_retention(
+        &self,
+        _input: PutObjectRetentionRequest,
+    ) -> Result<PutObjectRetentionOutput, RusotoError<PutObjectRetentionError>> {
+        unimplemented!("FakeS3::put_object_retention is not exercised by these tests")
+    }
+
+    async fn put_object_tagging(
+        &self,
+        _input: PutObjectTaggingRequest,
+    ) -> Result<PutObjectTaggingOutput, RusotoError<PutObjectTaggingError>> {
+        unimplemented!("FakeS3::put_object_tagging is not exercised by these tests")
+    }
+
+    async fn put_public_access_block(
+        &self,
+        _input: PutPublicAccessBlockRequest,
+    ) -> Result<(), RusotoError<PutPublicAccessBlockError>> {
+        unimplemented!("FakeS3::put_public_access_block is not exercised by these tests")
+    }
+
+    async fn restore_object(
+        &self,
+        _input: RestoreObjectRequest,
+    ) -> Result<RestoreObjectOutput, RusotoError<RestoreObjectError>> {
+        unimplemented!("FakeS3::restore_object is not exercised by these tests")
+    }
+
+    async fn select_object_content(
+        &self,
+        _input: SelectObjectContentRequest,
+    ) -> Result<SelectObjectContentOutput, RusotoError<SelectObjectContentError>> {
+        unimplemented!("FakeS3::select_object_content is not exercised by these tests")
+    }
+
+    async fn upload_part_copy(
+        &self,
+        _input: UploadPartCopyRequest,
+    ) -> Result<UploadPartCopyOutput, RusotoError<UploadPartCopyError>> {
+        unimplemented!("FakeS3::upload_part_copy is not exercised by these tests")
+    }
+
+    async fn write_get_object_response(
+        &self,
+        _input: WriteGetObjectResponseRequest,
+    ) -> Result<(), RusotoError<WriteGetObjectResponseError>> {
+        unimplemented!("FakeS3::write_get_object_response is not exercised by these tests")
+    }
+}
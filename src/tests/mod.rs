@@ -0,0 +1,768 @@
+mod fake_s3;
+
+use crate::retry::{self, Retry};
+use crate::sink::MultipartUploadSink;
+use crate::split::{self, Part};
+use crate::{MultipartUploadRequest, UploadProgress};
+use bytes::Bytes;
+use fake_s3::FakeS3;
+use futures::{stream, TryStreamExt};
+use rusoto_core::{HttpDispatchError, RusotoError};
+use rusoto_s3::{
+    CompleteMultipartUploadError, CompletedPart, CreateMultipartUploadError,
+    ListPartsError, Part as ListedPart, UploadPartError,
+};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+#[derive(Debug)]
+struct DummyError;
+
+/// The sink's error type needs to convert from every upstream `RusotoError` it can produce, plus
+/// `split::PartLimitExceeded` for the part-count cap; a real caller would define something
+/// equivalent for their own error type.
+#[derive(Debug)]
+enum SinkTestError {
+    Create(RusotoError<CreateMultipartUploadError>),
+    Upload(RusotoError<UploadPartError>),
+    Complete(RusotoError<CompleteMultipartUploadError>),
+    PartLimit(split::PartLimitExceeded),
+}
+
+impl std::fmt::Display for SinkTestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Create(e) => write!(f, "{e}"),
+            Self::Upload(e) => write!(f, "{e}"),
+            Self::Complete(e) => write!(f, "{e}"),
+            Self::PartLimit(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<RusotoError<CreateMultipartUploadError>> for SinkTestError {
+    fn from(e: RusotoError<CreateMultipartUploadError>) -> Self {
+        Self::Create(e)
+    }
+}
+
+impl From<RusotoError<UploadPartError>> for SinkTestError {
+    fn from(e: RusotoError<UploadPartError>) -> Self {
+        Self::Upload(e)
+    }
+}
+
+impl From<RusotoError<CompleteMultipartUploadError>> for SinkTestError {
+    fn from(e: RusotoError<CompleteMultipartUploadError>) -> Self {
+        Self::Complete(e)
+    }
+}
+
+impl From<split::PartLimitExceeded> for SinkTestError {
+    fn from(e: split::PartLimitExceeded) -> Self {
+        Self::PartLimit(e)
+    }
+}
+
+#[tokio::test]
+async fn sink_uploads_writes_as_parts_and_completes() {
+    let client = FakeS3::new();
+    let mut sink: MultipartUploadSink<'_, FakeS3, SinkTestError> = MultipartUploadSink::new(
+        &client,
+        "bucket".to_string(),
+        "key".to_string(),
+        4..=4,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Each write lands in the buffer behind the previous one; the previous write's bytes only
+    // get flushed into a part once this write observes the buffer at/over `part_size.start()`.
+    sink.write_all(&[0u8; 4]).await.unwrap();
+    sink.write_all(&[1u8; 4]).await.unwrap();
+    sink.write_all(&[2u8; 2]).await.unwrap();
+    sink.shutdown().await.unwrap();
+
+    let output = sink.take_output().unwrap().unwrap();
+    assert!(output.location.is_none());
+    assert_eq!(client.upload_part_calls(), vec![1, 2, 3]);
+    assert!(!client.was_aborted());
+}
+
+#[tokio::test]
+async fn sink_aborts_and_reports_the_error_on_an_upload_part_failure() {
+    let client = FakeS3::failing_at(2);
+    let mut sink: MultipartUploadSink<'_, FakeS3, SinkTestError> = MultipartUploadSink::new(
+        &client,
+        "bucket".to_string(),
+        "key".to_string(),
+        4..=4,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    sink.write_all(&[0u8; 4]).await.unwrap();
+    sink.write_all(&[1u8; 4]).await.unwrap();
+    sink.write_all(&[2u8; 4]).await.unwrap();
+    sink.shutdown().await.unwrap();
+
+    assert!(matches!(sink.take_output(), Some(Err(SinkTestError::Upload(_)))));
+    assert!(client.was_aborted());
+}
+
+/// Records whether it was ever woken, so a test can poll a future manually and tell a genuine
+/// deadlock (never woken) apart from a completion that just takes more than one poll.
+struct RecordingWaker(Arc<AtomicBool>);
+
+impl Wake for RecordingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn sink_flushes_under_a_concurrency_limit_without_deadlocking() {
+    let client = FakeS3::new();
+    let mut sink: MultipartUploadSink<'_, FakeS3, SinkTestError> = MultipartUploadSink::new(
+        &client,
+        "bucket".to_string(),
+        "key".to_string(),
+        4..=4,
+        Some(1),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Leave 8 bytes sitting in `buffer` across two writes so the second write's `poll_write`
+    // sees the while loop's pre-`buf` buffer at/over `part_size.start()` twice: once to push a
+    // part future, then again to hit `concurrency_limit` with that future still unpolled. A
+    // regression here previously returned `Pending` in that situation without ever polling the
+    // future it had just pushed, so no waker was ever registered for it and the write hung
+    // forever. `tokio::time::timeout` can't catch that: its deadline timer shares the write's
+    // `Context`, so when it fires it ends up polling the stuck future for the first time itself,
+    // masking the missing-waker bug instead of catching it. Drive the poll manually instead and
+    // check a wake is actually scheduled before polling again.
+    sink.write_all(&[0u8; 8]).await.unwrap();
+
+    let woken = Arc::new(AtomicBool::new(false));
+    let waker = Waker::from(Arc::new(RecordingWaker(woken.clone())));
+    let mut cx = Context::from_waker(&waker);
+    let mut write = Box::pin(sink.write_all(&[0u8; 4]));
+    match write.as_mut().poll(&mut cx) {
+        Poll::Ready(result) => result.unwrap(),
+        Poll::Pending => {
+            assert!(
+                woken.load(Ordering::SeqCst),
+                "poll_write returned Pending under concurrency_limit without scheduling a wake \
+                 for the part future it had just pushed"
+            );
+            match write.as_mut().poll(&mut cx) {
+                Poll::Ready(result) => result.unwrap(),
+                Poll::Pending => panic!("write did not complete after being woken"),
+            }
+        }
+    }
+    drop(write);
+
+    sink.shutdown().await.unwrap();
+
+    let output = sink.take_output().unwrap().unwrap();
+    assert!(output.location.is_none());
+    assert_eq!(client.upload_part_calls(), vec![1, 2, 3]);
+}
+
+/// `resume`'s error type needs to convert from every upstream `RusotoError` it can produce
+/// (including `ListPartsError`, which only `resume` itself deals with) plus
+/// `split::PartLimitExceeded` and `crate::NonContiguousParts`; a real caller would define
+/// something equivalent for their own error type.
+#[derive(Debug)]
+enum ResumeTestError {
+    Create(RusotoError<CreateMultipartUploadError>),
+    Upload(RusotoError<UploadPartError>),
+    Complete(RusotoError<CompleteMultipartUploadError>),
+    ListParts(RusotoError<ListPartsError>),
+    PartLimit(split::PartLimitExceeded),
+    NonContiguousParts(crate::NonContiguousParts),
+}
+
+impl std::fmt::Display for ResumeTestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Create(e) => write!(f, "{e}"),
+            Self::Upload(e) => write!(f, "{e}"),
+            Self::Complete(e) => write!(f, "{e}"),
+            Self::ListParts(e) => write!(f, "{e}"),
+            Self::PartLimit(e) => write!(f, "{e}"),
+            Self::NonContiguousParts(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<RusotoError<CreateMultipartUploadError>> for ResumeTestError {
+    fn from(e: RusotoError<CreateMultipartUploadError>) -> Self {
+        Self::Create(e)
+    }
+}
+
+impl From<RusotoError<UploadPartError>> for ResumeTestError {
+    fn from(e: RusotoError<UploadPartError>) -> Self {
+        Self::Upload(e)
+    }
+}
+
+impl From<RusotoError<CompleteMultipartUploadError>> for ResumeTestError {
+    fn from(e: RusotoError<CompleteMultipartUploadError>) -> Self {
+        Self::Complete(e)
+    }
+}
+
+impl From<RusotoError<ListPartsError>> for ResumeTestError {
+    fn from(e: RusotoError<ListPartsError>) -> Self {
+        Self::ListParts(e)
+    }
+}
+
+impl From<split::PartLimitExceeded> for ResumeTestError {
+    fn from(e: split::PartLimitExceeded) -> Self {
+        Self::PartLimit(e)
+    }
+}
+
+impl From<crate::NonContiguousParts> for ResumeTestError {
+    fn from(e: crate::NonContiguousParts) -> Self {
+        Self::NonContiguousParts(e)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+#[tokio::test]
+async fn resume_skips_existing_parts_and_merges_them_into_the_completed_list() {
+    // Parts 1-2 (20 bytes) are already uploaded; paginate one part per `list_parts` page so
+    // `resume`'s part_number_marker loop actually has to walk more than one page.
+    let client = FakeS3::with_existing_parts(
+        vec![
+            ListedPart {
+                e_tag: Some("etag-1".to_string()),
+                part_number: Some(1),
+                size: Some(10),
+                ..ListedPart::default()
+            },
+            ListedPart {
+                e_tag: Some("etag-2".to_string()),
+                part_number: Some(2),
+                size: Some(10),
+                ..ListedPart::default()
+            },
+        ],
+        1,
+    );
+
+    // The same, untrimmed stream the original upload would have read: the first 20 bytes match
+    // what parts 1-2 already cover, followed by 8 bytes still to upload.
+    let body = stream::iter(
+        [10, 10, 4, 4]
+            .into_iter()
+            .map(|n| Ok::<_, ResumeTestError>(Bytes::from(vec![0u8; n]))),
+    );
+
+    crate::resume(
+        &client,
+        MultipartUploadRequest {
+            body,
+            bucket: "bucket".to_string(),
+            key: "key".to_string(),
+            on_progress: None,
+        },
+        "fake-upload-id".to_string(),
+        4..=4,
+        None,
+        None,
+        None,
+        false,
+    )
+    .await
+    .unwrap();
+
+    // Only the 8 bytes past the already-uploaded parts were re-uploaded, numbered after them.
+    assert_eq!(client.upload_part_calls(), vec![3, 4]);
+    assert_eq!(
+        client
+            .completed_parts()
+            .unwrap()
+            .iter()
+            .map(|p| (p.part_number, p.e_tag.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (Some(1), Some("etag-1".to_string())),
+            (Some(2), Some("etag-2".to_string())),
+            (Some(3), Some("etag-3".to_string())),
+            (Some(4), Some("etag-4".to_string())),
+        ]
+    );
+}
+
+#[allow(clippy::result_large_err)]
+#[tokio::test]
+async fn resume_rejects_a_non_contiguous_part_range() {
+    // As under `concurrency_limit > 1`, S3 durably recorded part 3 before part 2 ever finished (or
+    // while it was still retrying), so a process killed at this point leaves a genuine gap.
+    let client = FakeS3::with_existing_parts(
+        vec![
+            ListedPart {
+                e_tag: Some("etag-1".to_string()),
+                part_number: Some(1),
+                size: Some(10),
+                ..ListedPart::default()
+            },
+            ListedPart {
+                e_tag: Some("etag-3".to_string()),
+                part_number: Some(3),
+                size: Some(10),
+                ..ListedPart::default()
+            },
+        ],
+        usize::MAX,
+    );
+
+    let body = stream::iter(
+        [10, 10, 10, 4]
+            .into_iter()
+            .map(|n| Ok::<_, ResumeTestError>(Bytes::from(vec![0u8; n]))),
+    );
+
+    let result = crate::resume(
+        &client,
+        MultipartUploadRequest {
+            body,
+            bucket: "bucket".to_string(),
+            key: "key".to_string(),
+            on_progress: None,
+        },
+        "fake-upload-id".to_string(),
+        4..=4,
+        None,
+        None,
+        None,
+        false,
+    )
+    .await;
+
+    assert!(matches!(result, Err(ResumeTestError::NonContiguousParts(_))));
+    // No part was re-uploaded, and the gap was never papered over by skipping bytes past it.
+    assert!(client.upload_part_calls().is_empty());
+}
+
+#[allow(clippy::result_large_err)]
+#[tokio::test]
+async fn multipart_upload_reports_progress_after_each_part() {
+    let client = FakeS3::new();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let on_progress = {
+        let events = events.clone();
+        Arc::new(move |progress: UploadProgress| events.lock().unwrap().push(progress))
+    };
+
+    let body = stream::iter(
+        [4, 4, 2]
+            .into_iter()
+            .map(|n| Ok::<_, ResumeTestError>(Bytes::from(vec![0u8; n]))),
+    );
+
+    crate::multipart_upload(
+        &client,
+        MultipartUploadRequest {
+            body,
+            bucket: "bucket".to_string(),
+            key: "key".to_string(),
+            on_progress: Some(on_progress),
+        },
+        4..=4,
+        None,
+        None,
+        None,
+        false,
+    )
+    .await
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    // `UploadProgress` is documented as completion order, not necessarily part order, so sort by
+    // part number before comparing the per-part fields...
+    let mut by_part_number = events
+        .iter()
+        .map(|p| (p.part_number, p.part_bytes))
+        .collect::<Vec<_>>();
+    by_part_number.sort();
+    assert_eq!(by_part_number, vec![(1, 4), (2, 4), (3, 2)]);
+    // ...but the running totals must still only grow, one part at a time, in whatever order the
+    // parts actually completed in.
+    assert!(events
+        .windows(2)
+        .all(|w| w[0].bytes_uploaded < w[1].bytes_uploaded && w[0].parts_completed + 1 == w[1].parts_completed));
+    let last = events.last().unwrap();
+    assert_eq!((last.bytes_uploaded, last.parts_completed), (10, 3));
+}
+
+#[allow(clippy::result_large_err)]
+#[tokio::test]
+async fn resume_seeds_progress_counters_from_existing_parts() {
+    // Parts 1-2 (20 bytes) were already uploaded before the process restarted; progress for the
+    // parts `resume` uploads itself must build on those totals rather than restart from zero.
+    let client = FakeS3::with_existing_parts(
+        vec![
+            ListedPart {
+                e_tag: Some("etag-1".to_string()),
+                part_number: Some(1),
+                size: Some(10),
+                ..ListedPart::default()
+            },
+            ListedPart {
+                e_tag: Some("etag-2".to_string()),
+                part_number: Some(2),
+                size: Some(10),
+                ..ListedPart::default()
+            },
+        ],
+        usize::MAX,
+    );
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let on_progress = {
+        let events = events.clone();
+        Arc::new(move |progress: UploadProgress| events.lock().unwrap().push(progress))
+    };
+
+    let body = stream::iter(
+        [10, 10, 4, 4]
+            .into_iter()
+            .map(|n| Ok::<_, ResumeTestError>(Bytes::from(vec![0u8; n]))),
+    );
+
+    crate::resume(
+        &client,
+        MultipartUploadRequest {
+            body,
+            bucket: "bucket".to_string(),
+            key: "key".to_string(),
+            on_progress: Some(on_progress),
+        },
+        "fake-upload-id".to_string(),
+        4..=4,
+        None,
+        None,
+        None,
+        false,
+    )
+    .await
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    let mut by_part_number = events
+        .iter()
+        .map(|p| (p.part_number, p.part_bytes))
+        .collect::<Vec<_>>();
+    by_part_number.sort();
+    assert_eq!(by_part_number, vec![(3, 4), (4, 4)]);
+    // Counters build on the 2 parts (20 bytes) `resume` found already uploaded, not on zero.
+    assert!(events
+        .windows(2)
+        .all(|w| w[0].bytes_uploaded < w[1].bytes_uploaded && w[0].parts_completed + 1 == w[1].parts_completed));
+    let last = events.last().unwrap();
+    assert_eq!((last.bytes_uploaded, last.parts_completed), (28, 4));
+}
+
+fn no_delay() -> Retry {
+    Retry {
+        max_attempts: 3,
+        initial_delay: Duration::from_millis(0),
+        max_delay: Duration::from_millis(0),
+    }
+}
+
+#[tokio::test]
+async fn with_retry_retries_retryable_errors_until_success() {
+    let mut attempts = 0;
+    let result: Result<&str, RusotoError<DummyError>> =
+        retry::with_retry(Some(no_delay()), || {
+            attempts += 1;
+            async move {
+                if attempts < 3 {
+                    Err(RusotoError::HttpDispatch(HttpDispatchError::new(
+                        "boom".to_string(),
+                    )))
+                } else {
+                    Ok("done")
+                }
+            }
+        })
+        .await;
+
+    assert_eq!(result.unwrap(), "done");
+    assert_eq!(attempts, 3);
+}
+
+#[tokio::test]
+async fn with_retry_gives_up_after_max_attempts() {
+    let mut attempts = 0;
+    let result: Result<(), RusotoError<DummyError>> = retry::with_retry(Some(no_delay()), || {
+        attempts += 1;
+        async {
+            Err(RusotoError::HttpDispatch(HttpDispatchError::new(
+                "boom".to_string(),
+            )))
+        }
+    })
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts, no_delay().max_attempts);
+}
+
+#[tokio::test]
+async fn with_retry_does_not_retry_non_retryable_errors() {
+    let mut attempts = 0;
+    let result: Result<(), RusotoError<DummyError>> = retry::with_retry(Some(no_delay()), || {
+        attempts += 1;
+        async { Err(RusotoError::Validation("bad input".to_string())) }
+    })
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts, 1);
+}
+
+fn fixed_chunks(sizes: &[usize]) -> impl futures::Stream<Item = Result<Bytes, split::PartLimitExceeded>> {
+    stream::iter(
+        sizes
+            .iter()
+            .map(|&n| Ok(Bytes::from(vec![0u8; n])))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn one_byte_chunks(n: usize) -> impl futures::Stream<Item = Result<Bytes, split::PartLimitExceeded>> {
+    stream::iter((0..n).map(|_| Ok(Bytes::from_static(&[0]))).collect::<Vec<_>>())
+}
+
+#[tokio::test]
+async fn split_accumulates_chunks_up_to_the_part_size_floor() {
+    let parts: Vec<Part> = split::split(
+        fixed_chunks(&[3 << 20, 3 << 20, 3 << 20]),
+        (5 << 20)..=(10 << 20),
+        false,
+        0,
+        0,
+        5 << 20,
+    )
+    .try_collect()
+    .await
+    .unwrap();
+
+    assert_eq!(
+        parts.iter().map(|p| p.content_length).collect::<Vec<_>>(),
+        vec![6 << 20, 3 << 20]
+    );
+    assert_eq!(
+        parts.iter().map(|p| p.part_number).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+}
+
+#[tokio::test]
+async fn split_adaptive_part_size_doubles_at_power_of_two_boundaries() {
+    let parts: Vec<Part> = split::split(
+        one_byte_chunks(1 + 2 + 4 + 4 + 8 + 8),
+        1..=8,
+        true,
+        0,
+        0,
+        1,
+    )
+    .try_collect()
+    .await
+    .unwrap();
+
+    assert_eq!(
+        parts.iter().map(|p| p.content_length).collect::<Vec<_>>(),
+        vec![1, 2, 4, 4, 8, 8]
+    );
+}
+
+#[tokio::test]
+async fn split_adaptive_errors_instead_of_exceeding_the_part_limit() {
+    let result: Result<Vec<Part>, split::PartLimitExceeded> = split::split(
+        one_byte_chunks(10_001),
+        1..=1,
+        true,
+        0,
+        0,
+        1,
+    )
+    .try_collect()
+    .await;
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "multipart upload would exceed the 10000-part limit even at the maximum part size"
+    );
+}
+
+#[tokio::test]
+async fn split_reslices_oversized_pending_against_the_next_parts_max() {
+    // A single chunk far larger than one part's max forces most of it into `pending`; that
+    // leftover must still be capped per part instead of being emitted as one oversized part.
+    let parts: Vec<Part> = split::split(fixed_chunks(&[5]), 1..=1, true, 0, 0, 1)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        parts.iter().map(|p| p.content_length).collect::<Vec<_>>(),
+        vec![1, 1, 1, 1, 1]
+    );
+    assert_eq!(
+        parts.iter().map(|p| p.part_number).collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5]
+    );
+}
+
+#[tokio::test]
+async fn split_skips_leading_bytes_and_resumes_numbering() {
+    // As if parts 1 and 2 (totalling 8 bytes) were already uploaded: resume after them. The
+    // stream still carries its full, untrimmed bytes (matching `split`'s `skip_bytes` contract),
+    // so it must cover parts 1-4, not just the two left to upload.
+    let parts: Vec<Part> = split::split(
+        one_byte_chunks(4 + 4 + 4 + 4),
+        4..=4,
+        false,
+        2,
+        8,
+        4,
+    )
+    .try_collect()
+    .await
+    .unwrap();
+
+    assert_eq!(
+        parts.iter().map(|p| p.part_number).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    assert_eq!(
+        parts.iter().map(|p| p.content_length).collect::<Vec<_>>(),
+        vec![4, 4]
+    );
+}
+
+#[test]
+fn merge_list_parts_page_accumulates_skip_bytes_and_max_part_number() {
+    let mut completed_parts = Vec::new();
+    let mut skip_bytes = 0;
+    let mut start_part_number = 0;
+    let mut start_part_size = 5 << 20;
+
+    crate::merge_list_parts_page(
+        vec![
+            ListedPart {
+                e_tag: Some("etag-1".to_string()),
+                part_number: Some(1),
+                size: Some(5 << 20),
+                ..ListedPart::default()
+            },
+            ListedPart {
+                e_tag: Some("etag-2".to_string()),
+                part_number: Some(2),
+                size: Some(8 << 20),
+                ..ListedPart::default()
+            },
+        ],
+        &mut completed_parts,
+        &mut skip_bytes,
+        &mut start_part_number,
+        &mut start_part_size,
+    );
+
+    assert_eq!(skip_bytes, 13 << 20);
+    assert_eq!(start_part_number, 2);
+    assert_eq!(start_part_size, 8 << 20);
+    assert_eq!(
+        completed_parts
+            .iter()
+            .map(|p| (p.part_number, p.e_tag.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (Some(1), Some("etag-1".to_string())),
+            (Some(2), Some("etag-2".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn merge_list_parts_page_keeps_the_largest_part_size_across_pages() {
+    let mut completed_parts: Vec<CompletedPart> = Vec::new();
+    let mut skip_bytes = 0;
+    let mut start_part_number = 0;
+    let mut start_part_size = 5 << 20;
+
+    // First page: a small trailing part (as `list_parts` might return after pagination).
+    crate::merge_list_parts_page(
+        vec![ListedPart {
+            part_number: Some(3),
+            size: Some(1 << 20),
+            ..ListedPart::default()
+        }],
+        &mut completed_parts,
+        &mut skip_bytes,
+        &mut start_part_number,
+        &mut start_part_size,
+    );
+    // Second page: a larger, later-numbered part.
+    crate::merge_list_parts_page(
+        vec![ListedPart {
+            part_number: Some(4),
+            size: Some(8 << 20),
+            ..ListedPart::default()
+        }],
+        &mut completed_parts,
+        &mut skip_bytes,
+        &mut start_part_number,
+        &mut start_part_size,
+    );
+
+    assert_eq!(start_part_size, 8 << 20);
+    assert_eq!(start_part_number, 4);
+}
+
+#[tokio::test]
+async fn with_retry_none_runs_exactly_once_even_on_failure() {
+    let mut attempts = 0;
+    let result: Result<(), RusotoError<DummyError>> = retry::with_retry(None, || {
+        attempts += 1;
+        async {
+            Err(RusotoError::HttpDispatch(HttpDispatchError::new(
+                "boom".to_string(),
+            )))
+        }
+    })
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts, 1);
+}